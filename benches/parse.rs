@@ -0,0 +1,42 @@
+//! Parse and extract throughput for the activity pages, for one date and for a range of dates
+
+use chrono::{TimeZone, Utc};
+use criterion::{criterion_group, criterion_main, Criterion};
+use sheepnet::{get_daily_data, get_weekly_data, ActivityPage, Language};
+use std::hint::black_box;
+
+const DAILY_FIXTURE: &str = include_str!("../tests/fixtures/daily_activities.html");
+const WEEKLY_FIXTURE: &str = include_str!("../tests/fixtures/weekly_activities.html");
+
+fn parse_benchmarks(c: &mut Criterion) {
+    let date = Utc.with_ymd_and_hms(2025, 11, 22, 16, 0, 0).unwrap();
+    // Every day of the fixture from 18 to 30 November 2025
+    let dates: Vec<_> = (18..=30).map(|day| Utc.with_ymd_and_hms(2025, 11, day, 16, 0, 0).unwrap()).collect();
+
+    c.bench_function("daily_single_date", |b| {
+        b.iter(|| get_daily_data(black_box(DAILY_FIXTURE), &date, &date, false, Language::En).unwrap())
+    });
+    c.bench_function("weekly_single_date", |b| {
+        b.iter(|| get_weekly_data(black_box(WEEKLY_FIXTURE), &date, Language::En).unwrap())
+    });
+
+    // What a multi-date render did before: one parse per date
+    c.bench_function("daily_range_reparse", |b| {
+        b.iter(|| {
+            for date in &dates {
+                get_daily_data(black_box(DAILY_FIXTURE), date, date, false, Language::En).unwrap();
+            }
+        })
+    });
+    c.bench_function("daily_range_parse_once", |b| {
+        b.iter(|| {
+            let page = ActivityPage::parse(black_box(DAILY_FIXTURE)).unwrap();
+            for date in &dates {
+                page.daily_data(date, date, false, Language::En).unwrap();
+            }
+        })
+    });
+}
+
+criterion_group!(benches, parse_benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,40 @@
+//! Records the git commit and the build date for `sheepnet --version`.
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    // Builds from a source tarball have no git checkout; CI exports the commit as GITHUB_SHA
+    let sha = Command::new("git")
+        .args(["rev-parse", "--short=10", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|sha| sha.trim().to_string())
+        .or_else(|| std::env::var("GITHUB_SHA").ok().map(|sha| sha.chars().take(10).collect()))
+        .filter(|sha| !sha.is_empty())
+        .unwrap_or_else(|| "unknown commit".to_string());
+
+    // SOURCE_DATE_EPOCH makes the date reproducible (https://reproducible-builds.org/specs/source-date-epoch/)
+    let date = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse().ok())
+        .and_then(|epoch| chrono::DateTime::from_timestamp(epoch, 0))
+        .unwrap_or_else(chrono::Utc::now);
+
+    println!("cargo:rustc-env=SHEEPNET_GIT_SHA={}", sha);
+    println!("cargo:rustc-env=SHEEPNET_BUILD_DATE={}", date.format("%Y-%m-%d %H:%M UTC"));
+
+    // Rebuild the version when the checked out commit changes
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    println!("cargo:rerun-if-env-changed=GITHUB_SHA");
+    // (only for files that exist, cargo would rebuild every time otherwise)
+    let head = std::fs::read_to_string(".git/HEAD").unwrap_or_default();
+    let reference = head.strip_prefix("ref: ").map(|reference| format!(".git/{}", reference.trim()));
+    for path in [Some(".git/HEAD".to_string()), reference, Some(".git/packed-refs".to_string())].into_iter().flatten() {
+        if Path::new(&path).exists() {
+            println!("cargo:rerun-if-changed={}", path);
+        }
+    }
+}
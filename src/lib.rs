@@ -0,0 +1,804 @@
+//! Parsing of the Guild Wars Wiki's daily and weekly activity pages, shared by the bot and the benchmarks
+
+use anyhow::{Context as AnyhowContext, Result};
+use chrono::{DateTime, Datelike, Duration, Locale, NaiveDate, TimeZone, Utc};
+use clap::ValueEnum;
+use regex::Regex;
+use scraper::{Html, Selector};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+pub const WIKI_BASE_URL: &str = "https://wiki.guildwars.com";
+pub const DAILY_PAGE: &str = "Daily_activities";
+pub const WEEKLY_PAGE: &str = "Weekly_activities";
+/// Festivals recognized on the events page (--include-events)
+const FESTIVALS: [&str; 9] = [
+    "Canthan New Year",
+    "Lucky Treats Week",
+    "Sweet Treats Week",
+    "Anniversary Celebration",
+    "Dragon Festival",
+    "Wayfarer's Reverie",
+    "Pirate Week",
+    "Halloween",
+    "Wintersday",
+];
+/// Regions recognized in a plain-text suffix of an area cell, e.g. "Nahpui Quarter (Kaineng City)"
+const REGIONS: [&str; 24] = [
+    "Ascalon",
+    "Northern Shiverpeaks",
+    "Kryta",
+    "Maguuma Jungle",
+    "Crystal Desert",
+    "Southern Shiverpeaks",
+    "Ring of Fire Islands",
+    "Shing Jea Island",
+    "Kaineng City",
+    "Echovald Forest",
+    "The Jade Sea",
+    "Istan",
+    "Kourna",
+    "Vabbi",
+    "The Desolation",
+    "Realm of Torment",
+    "Charr Homelands",
+    "Tarnished Coast",
+    "Depths of Tyria",
+    "Far Shiverpeaks",
+    "Battle Isles",
+    "The Mists",
+    "Fissure of Woe",
+    "The Underworld",
+];
+
+// Bundled English -> localized activity names for --lang
+const TRANSLATIONS_DE: &str = include_str!("../translations/de.json");
+
+/// How wiki links in an activity are rendered
+#[derive(Debug, Clone, ValueEnum)]
+pub enum LinkStyle {
+    /// Markdown links: [text](url)
+    Markdown,
+    /// Link text only, no URLs
+    Plain,
+    /// HTML anchors: <a href="url">text</a>
+    Html,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Language {
+    /// English (as on the wiki)
+    En,
+    /// German
+    De,
+}
+
+impl Language {
+    /// Load the bundled name mapping, None for English
+    pub fn translations(&self) -> Result<Option<HashMap<String, String>>> {
+        let json = match self {
+            Language::En => return Ok(None),
+            Language::De => TRANSLATIONS_DE,
+        };
+        let map = serde_json::from_str(json).with_context(|| format!("Invalid bundled translations for {:?}", self))?;
+        Ok(Some(map))
+    }
+
+    /// Base URL of this language's edition of the wiki
+    pub fn wiki_base_url(&self) -> &'static str {
+        match self {
+            Language::En => WIKI_BASE_URL,
+            Language::De => "https://wiki-de.guildwars.com",
+        }
+    }
+
+    /// Titles of the daily and weekly activity pages on this language's wiki
+    pub fn activity_pages(&self) -> (&'static str, &'static str) {
+        match self {
+            Language::En => (DAILY_PAGE, WEEKLY_PAGE),
+            Language::De => ("Tägliche_Aktivitäten", "Wöchentliche_Aktivitäten"),
+        }
+    }
+
+    /// The activity (as in DailyColumns) a header of this wiki's daily activities table names
+    pub fn daily_column(&self, header: &str) -> Option<&'static str> {
+        let key = match (self, header) {
+            (Language::De, "Zaishen-Mission") => "zm",
+            (Language::De, "Zaishen-Kopfgeld") => "zb",
+            (Language::De, "Zaishen-Kampf") => "zc",
+            (Language::De, "Zaishen-Bezwingung") => "zv",
+            (Language::De, "Glänzende Klinge" | "Gesucht") => "sb",
+            (Language::De, "Vorhut-Quest") => "vq",
+            (_, "Zaishen Mission") => "zm",
+            (_, "Zaishen Bounty") => "zb",
+            (_, "Zaishen Combat") => "zc",
+            (_, "Zaishen Vanquish") => "zv",
+            (_, "Shining Blade" | "Wanted") => "sb",
+            (_, "Vanguard Quest") => "vq",
+            (_, "Nicholas Sandford") => "ns",
+            _ => return None,
+        };
+        Some(key)
+    }
+
+    /// A date as written in the first column of this wiki's activity tables
+    pub fn format_wiki_date(&self, date: &DateTime<Utc>) -> String {
+        match self {
+            Language::En => date.format("%-d %B %Y").to_string(),
+            Language::De => date.format_localized("%-d. %B %Y", Locale::de_DE).to_string(),
+        }
+    }
+}
+
+pub fn get_current_daily_date(now: &DateTime<Utc>) -> DateTime<Utc> {
+    // Dailies change at 16:00 UTC
+    // If current time is before 16:00, use yesterday's date
+    let daily_cutoff = Utc
+        .with_ymd_and_hms(now.year(), now.month(), now.day(), 16, 0, 0)
+        .unwrap();
+
+    if *now < daily_cutoff {
+        // Before 16:00:00 UTC - use previous day
+        *now - Duration::days(1)
+    } else {
+        // At or after 16:00:00 UTC - use current day
+        *now
+    }
+}
+
+pub fn get_nicholas_sandford_date(now: &DateTime<Utc>) -> DateTime<Utc> {
+    // Nicholas Sandford changes at 07:00 UTC
+    // If current time is before 07:00, use yesterday's date
+    let ns_cutoff = Utc
+        .with_ymd_and_hms(now.year(), now.month(), now.day(), 7, 0, 0)
+        .unwrap();
+
+    if *now < ns_cutoff {
+        // Before 07:00 UTC - use previous day
+        *now - Duration::days(1)
+    } else {
+        // After 07:00 UTC - use current day
+        *now
+    }
+}
+
+pub fn get_current_weekly_date(now: &DateTime<Utc>) -> DateTime<Utc> {
+    // Weekly activities change at 15:00 UTC on Mondays
+    // If current time is before 15:00, use the previous weekly period
+    let weekly_cutoff = Utc
+        .with_ymd_and_hms(now.year(), now.month(), now.day(), 15, 0, 0)
+        .unwrap();
+
+    let base_time = Utc
+        .with_ymd_and_hms(2025, 2, 10, 15, 0, 0)
+        .unwrap()
+        .timestamp();
+    let current_time = if *now < weekly_cutoff {
+        (*now - Duration::days(1)).timestamp()
+    } else {
+        now.timestamp()
+    };
+    let one_week = 3600 * 24 * 7;
+
+    let mut target_time = base_time;
+    while (target_time + one_week) < current_time {
+        target_time += one_week;
+    }
+
+    DateTime::from_timestamp(target_time, 0).unwrap()
+}
+
+
+/// Failures while extracting activities from the wiki pages
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SheepnetError {
+    #[error("Page body is empty")]
+    EmptyBody,
+    #[error("Page doesn't look like a wiki article (error page?): {0}")]
+    NotWikiPage(String),
+    #[error("Wiki page does not exist (renamed or wrong URL?)")]
+    PageDoesNotExist,
+    #[error("Could not find table tbody")]
+    TableNotFound,
+    #[error("No daily data found for {0}")]
+    DailyRowNotFound(String),
+    #[error("No Nicholas Sandford data found for {0}")]
+    NsNotFound(String),
+    #[error("No weekly data found for {0}")]
+    WeeklyRowNotFound(String),
+    #[error("Weekly table has no \"{0}\" column header")]
+    WeeklyHeaderMissing(String),
+    #[error("Empty activities: {}", .0.join(", "))]
+    EmptyFields(Vec<String>),
+    #[error("Dailies are for {found}, expected {expected}")]
+    DateMismatch { found: String, expected: String },
+}
+
+impl SheepnetError {
+    /// Machine-readable name of the error, for --json-errors
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SheepnetError::EmptyBody => "empty_body",
+            SheepnetError::NotWikiPage(_) => "not_wiki_page",
+            SheepnetError::PageDoesNotExist => "page_does_not_exist",
+            SheepnetError::TableNotFound => "table_not_found",
+            SheepnetError::DailyRowNotFound(_) => "daily_row_not_found",
+            SheepnetError::NsNotFound(_) => "ns_not_found",
+            SheepnetError::WeeklyRowNotFound(_) => "weekly_row_not_found",
+            SheepnetError::WeeklyHeaderMissing(_) => "weekly_header_missing",
+            SheepnetError::EmptyFields(_) => "empty_fields",
+            SheepnetError::DateMismatch { .. } => "date_mismatch",
+        }
+    }
+}
+
+pub type ParseResult<T> = std::result::Result<T, SheepnetError>;
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct DailyData {
+    pub ns: String,
+    pub vq: String,
+    pub sb: String,
+    pub zm: String,
+    pub zb: String,
+    pub zc: String,
+    pub zv: String,
+    /// Region of the Zaishen Bounty target, if the wiki cell names it
+    pub zb_region: Option<String>,
+    /// Region of the Zaishen Vanquish area, if the wiki cell names it
+    pub zv_region: Option<String>,
+    /// Activities from columns added to the wiki table after the ones above, keyed by header
+    pub extras: HashMap<String, String>,
+    /// Non-fatal problems found while parsing (e.g. Nicholas Sandford not yet published)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// The wiki page's "This page was last edited on ..." footer, if it has one
+    #[serde(skip)]
+    pub last_edited: Option<String>,
+    /// The festival going on, with --include-events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<String>,
+    /// The date cell of the row the dailies were read from
+    #[serde(skip)]
+    pub row_date: String,
+    /// Retries it took to fetch the two pages, for --show-retry-notice
+    #[serde(skip)]
+    pub fetch_retries: u32,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct WeeklyData {
+    pub ni: String,
+    pub pve: String,
+    pub pvp: String,
+}
+
+/// Column positions in the daily activities table
+#[derive(Debug, PartialEq)]
+struct DailyColumns {
+    zm: usize,
+    zb: usize,
+    zc: usize,
+    zv: usize,
+    sb: usize,
+    vq: usize,
+    ns: usize,
+    /// Columns with unknown headers (index, header text)
+    extras: Vec<(usize, String)>,
+}
+
+impl Default for DailyColumns {
+    fn default() -> Self {
+        DailyColumns {
+            zm: 1,
+            zb: 2,
+            zc: 3,
+            zv: 4,
+            sb: 5,
+            vq: 6,
+            ns: 7,
+            extras: Vec::new(),
+        }
+    }
+}
+
+impl DailyColumns {
+    /// Map the header row (in the wiki's language, or English) to column positions, falling back to the classic layout
+    fn from_header(tbody: &scraper::ElementRef, lang: Language) -> Self {
+        let mut columns = DailyColumns::default();
+        let Some(header) = tbody
+            .select(tr_selector())
+            .find(|tr| tr.select(th_selector()).next().is_some())
+        else {
+            return columns;
+        };
+
+        let names: Vec<String> = header
+            .child_elements()
+            .map(|th| th.text().collect::<String>().trim().to_string())
+            .collect();
+
+        for (index, name) in names.iter().enumerate().skip(1) {
+            match lang.daily_column(name) {
+                Some("zm") => columns.zm = index,
+                Some("zb") => columns.zb = index,
+                Some("zc") => columns.zc = index,
+                Some("zv") => columns.zv = index,
+                Some("sb") => columns.sb = index,
+                Some("vq") => columns.vq = index,
+                Some("ns") => columns.ns = index,
+                _ => {
+                    let name = if name.is_empty() { format!("Column {}", index + 1) } else { name.clone() };
+                    columns.extras.push((index, name));
+                }
+            }
+        }
+
+        columns
+    }
+
+    /// Rows need at least the known columns; extra columns are optional
+    fn min_cells(&self) -> usize {
+        [self.zm, self.zb, self.zc, self.zv, self.sb, self.vq, self.ns]
+            .into_iter()
+            .max()
+            .unwrap_or(0)
+            + 1
+    }
+}
+
+/// Column positions in the weekly activities table
+#[derive(Debug, PartialEq)]
+struct WeeklyColumns {
+    pve: usize,
+    pvp: usize,
+    ni: usize,
+}
+
+impl WeeklyColumns {
+    /// Map the header row to column positions.
+    ///
+    /// Headers are matched case-insensitively on "PvE", "PvP" and "Nicholas", the first Nicholas
+    /// column being the item (location and map follow it).
+    fn from_header(tbody: &scraper::ElementRef) -> ParseResult<Self> {
+        let header = tbody
+            .select(tr_selector())
+            .find(|tr| tr.select(th_selector()).next().is_some())
+            .ok_or_else(|| SheepnetError::WeeklyHeaderMissing("Week starting".to_string()))?;
+
+        let names: Vec<String> = header
+            .child_elements()
+            .map(|th| th.text().collect::<String>().trim().to_lowercase())
+            .collect();
+        let find = |needle: &str, label: &str| {
+            names
+                .iter()
+                .enumerate()
+                .skip(1)
+                .find(|(_, name)| name.contains(needle))
+                .map(|(index, _)| index)
+                .ok_or_else(|| SheepnetError::WeeklyHeaderMissing(label.to_string()))
+        };
+
+        Ok(WeeklyColumns {
+            pve: find("pve", "PvE bonus")?,
+            pvp: find("pvp", "PvP bonus")?,
+            ni: find("nicholas", "Nicholas item")?,
+        })
+    }
+
+    fn min_cells(&self) -> usize {
+        self.pve.max(self.pvp).max(self.ni) + 1
+    }
+}
+
+/// The date in a table's first cell, from all of its text (also inside links and spans),
+/// without zero-width characters and with typographic apostrophes made straight
+fn cell_date_text(cell: &scraper::ElementRef) -> String {
+    let text: String = cell
+        .text()
+        .flat_map(str::chars)
+        .filter(|c| !matches!(c, '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}'))
+        .map(|c| if matches!(c, '\u{2018}' | '\u{2019}') { '\'' } else { c })
+        .collect();
+    normalize_whitespace(&text)
+}
+
+/// Collapse runs of whitespace (including non-breaking spaces) into single spaces and trim the ends
+pub fn normalize_whitespace(text: &str) -> String {
+    // char::is_whitespace already covers U+00A0, the replace keeps that explicit
+    text.replace('\u{00A0}', " ").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Reject bodies that can't contain an activity table, e.g. an empty response or an error page
+fn check_wiki_body(body: &str) -> ParseResult<()> {
+    let trimmed = body.trim();
+    if trimmed.is_empty() {
+        return Err(SheepnetError::EmptyBody);
+    }
+    // MediaWiki answers a missing article with a 200 page saying "There is currently no text in this page"
+    if trimmed.contains("class=\"noarticletext") || trimmed.contains("There is currently no text in this page") {
+        return Err(SheepnetError::PageDoesNotExist);
+    }
+    if !trimmed.contains("<table") && !trimmed.contains("mw-parser-output") {
+        let start: String = normalize_whitespace(trimmed).chars().take(80).collect();
+        return Err(SheepnetError::NotWikiPage(start));
+    }
+    Ok(())
+}
+
+/// Selectors are compiled on first use and shared by all parses
+pub fn cached_selector(cell: &'static OnceLock<Selector>, css: &str) -> &'static Selector {
+    cell.get_or_init(|| Selector::parse(css).unwrap())
+}
+
+/// Same for the regexes that run on every table cell
+pub fn cached_regex(cell: &'static OnceLock<Regex>, pattern: &str) -> &'static Regex {
+    cell.get_or_init(|| Regex::new(pattern).unwrap())
+}
+
+static TBODY_SELECTOR: OnceLock<Selector> = OnceLock::new();
+static TR_SELECTOR: OnceLock<Selector> = OnceLock::new();
+static TH_SELECTOR: OnceLock<Selector> = OnceLock::new();
+static A_SELECTOR: OnceLock<Selector> = OnceLock::new();
+static LASTMOD_SELECTOR: OnceLock<Selector> = OnceLock::new();
+
+fn tbody_selector() -> &'static Selector {
+    cached_selector(&TBODY_SELECTOR, "div.mw-parser-output table tbody")
+}
+
+fn tr_selector() -> &'static Selector {
+    cached_selector(&TR_SELECTOR, "tr")
+}
+
+fn th_selector() -> &'static Selector {
+    cached_selector(&TH_SELECTOR, "th")
+}
+
+fn a_selector() -> &'static Selector {
+    cached_selector(&A_SELECTOR, "a")
+}
+
+/// The cells of the table row for the date `search`.
+///
+/// Rows with fewer than `min_cells` cells and header rows (`<th>` after the date cell) are skipped.
+/// If several rows carry the date, e.g. a placeholder row before the real one, the row with the most
+/// linked cells wins, and the first of those on a tie.
+fn find_date_row<'a>(tbody: &scraper::ElementRef<'a>, search: &str, min_cells: usize) -> Option<Vec<scraper::ElementRef<'a>>> {
+    tbody
+        .select(tr_selector())
+        .map(|tr| tr.child_elements().collect::<Vec<_>>())
+        .filter(|cells| cells.len() >= min_cells && cells.len() > 1)
+        .filter(|cells| cells[1..].iter().all(|cell| cell.value().name() != "th"))
+        .filter(|cells| cell_date_text(&cells[0]) == search)
+        .enumerate()
+        .max_by_key(|(index, cells)| {
+            let links = cells[1..].iter().filter(|cell| cell.select(a_selector()).next().is_some()).count();
+            (links, std::cmp::Reverse(*index))
+        })
+        .map(|(_, cells)| cells)
+}
+
+fn lastmod_selector() -> &'static Selector {
+    cached_selector(&LASTMOD_SELECTOR, "#lastmod")
+}
+
+/// A parsed activities page, so several dates can be looked up without parsing the HTML again.
+///
+/// The document isn't `Send`; parse and extract between two awaits.
+pub struct ActivityPage {
+    document: Html,
+}
+
+impl ActivityPage {
+    pub fn parse(body: &str) -> ParseResult<Self> {
+        check_wiki_body(body)?;
+        Ok(ActivityPage { document: Html::parse_document(body) })
+    }
+
+    fn table(&self) -> ParseResult<scraper::ElementRef<'_>> {
+        self.document.select(tbody_selector()).next().ok_or(SheepnetError::TableNotFound)
+    }
+
+    /// The wiki footer's "This page was last edited on ..." sentence, in the wiki's language
+    pub fn last_edited(&self) -> Option<String> {
+        let text = normalize_whitespace(&self.document.select(lastmod_selector()).next()?.text().collect::<String>());
+        (!text.is_empty()).then_some(text)
+    }
+
+    /// The dailies of `daily_date` and the Nicholas Sandford of `ns_date`, see [`get_daily_data`]
+    pub fn daily_data(
+        &self,
+        daily_date: &DateTime<Utc>,
+        ns_date: &DateTime<Utc>,
+        require_ns: bool,
+        lang: Language,
+    ) -> ParseResult<DailyData> {
+        let daily_search = normalize_whitespace(&lang.format_wiki_date(daily_date));
+        let ns_search = normalize_whitespace(&lang.format_wiki_date(ns_date));
+        let tbody = self.table()?;
+        let columns = DailyColumns::from_header(&tbody, lang);
+        let mut daily_data = DailyData { last_edited: self.last_edited(), ..Default::default() };
+
+        // First pass: get regular dailies (16:00 UTC)
+        let cells = find_date_row(&tbody, &daily_search, columns.min_cells())
+            .ok_or(SheepnetError::DailyRowNotFound(daily_search))?;
+        daily_data.row_date = cell_date_text(&cells[0]);
+        daily_data.zm = convert_link(&get_html(&cells[columns.zm]))?;
+        (daily_data.zb, daily_data.zb_region) = parse_area_cell(&get_html(&cells[columns.zb]))?;
+        daily_data.zc = convert_link(&get_html(&cells[columns.zc]))?;
+        (daily_data.zv, daily_data.zv_region) = parse_area_cell(&get_html(&cells[columns.zv]))?;
+        daily_data.sb = convert_link(&get_html(&cells[columns.sb]))?;
+        daily_data.vq = convert_link(&get_html(&cells[columns.vq]))?;
+        for (index, name) in &columns.extras {
+            if let Some(cell) = cells.get(*index) {
+                daily_data.extras.insert(name.clone(), convert_link(&get_html(cell))?);
+            }
+        }
+
+        // Second pass: get Nicholas Sandford (07:00 UTC)
+        if let Some(cells) = find_date_row(&tbody, &ns_search, columns.min_cells()) {
+            daily_data.ns = convert_link(&get_html(&cells[columns.ns]))?;
+        } else {
+            if require_ns {
+                return Err(SheepnetError::NsNotFound(ns_search));
+            }
+            daily_data
+                .warnings
+                .push(SheepnetError::NsNotFound(ns_search).to_string());
+        }
+
+        Ok(daily_data)
+    }
+
+    /// The weekly bonuses that are current at `now`, see [`get_weekly_data`]
+    pub fn weekly_data(&self, now: &DateTime<Utc>, lang: Language) -> ParseResult<WeeklyData> {
+        self.weekly_data_for(&get_current_weekly_date(now), lang)
+    }
+
+    /// The weekly bonuses of the period starting on `weekly_date`'s day
+    pub fn weekly_data_for(&self, weekly_date: &DateTime<Utc>, lang: Language) -> ParseResult<WeeklyData> {
+        let search = normalize_whitespace(&lang.format_wiki_date(weekly_date));
+        let tbody = self.table()?;
+        let columns = WeeklyColumns::from_header(&tbody)?;
+
+        let cells = find_date_row(&tbody, &search, columns.min_cells()).ok_or(SheepnetError::WeeklyRowNotFound(search))?;
+
+        Ok(WeeklyData {
+            pve: render_cell(&get_html(&cells[columns.pve]), &LinkStyle::Plain)?,
+            pvp: render_cell(&get_html(&cells[columns.pvp]), &LinkStyle::Plain)?,
+            ni: render_cell(&get_html(&cells[columns.ni]), &LinkStyle::Markdown)?,
+        })
+    }
+}
+
+/// Parse the daily activities table.
+///
+/// A missing Nicholas Sandford row only produces a warning and an empty `ns`
+/// unless `require_ns` is set, since the wiki often lags behind the 07:00 UTC change.
+pub fn get_daily_data(
+    body: &str,
+    daily_date: &DateTime<Utc>,
+    ns_date: &DateTime<Utc>,
+    require_ns: bool,
+    lang: Language,
+) -> ParseResult<DailyData> {
+    ActivityPage::parse(body)?.daily_data(daily_date, ns_date, require_ns, lang)
+}
+
+pub fn get_weekly_data(body: &str, now: &DateTime<Utc>, lang: Language) -> ParseResult<WeeklyData> {
+    ActivityPage::parse(body)?.weekly_data(now, lang)
+}
+
+/// Parse the weekly bonuses of the period starting on `weekly_date`'s day, e.g. next week's
+pub fn get_weekly_data_for(body: &str, weekly_date: &DateTime<Utc>, lang: Language) -> ParseResult<WeeklyData> {
+    ActivityPage::parse(body)?.weekly_data_for(weekly_date, lang)
+}
+
+/// Render the HTML of a wiki table cell with the given link style.
+///
+/// This is the single entry point for cell conversion; `convert_link`, `strip_link`
+/// and the HTML style are the building blocks it dispatches to.
+pub fn render_cell(html: &str, style: &LinkStyle) -> ParseResult<String> {
+    match style {
+        LinkStyle::Markdown => convert_link(html),
+        LinkStyle::Plain => strip_link(html),
+        LinkStyle::Html => Ok(markdown_to_html_links(&convert_link(html)?)),
+    }
+}
+
+/// Convert the first link in a wiki cell to a markdown link with an absolute wiki URL.
+///
+/// Text after the link (e.g. " (3x)") is kept, cells without a link are returned with all tags removed.
+pub fn convert_link(html: &str) -> ParseResult<String> {
+    // Match <a> tags with href attribute (in any position)
+    static LINK_RE: OnceLock<Regex> = OnceLock::new();
+    let link_re = cached_regex(&LINK_RE, r#"<a\s+[^>]*href="([^"]+)"[^>]*>(.+?)</a>"#);
+    if let Some(caps) = link_re.captures(html) {
+        let url = &caps[1];
+        let text = &caps[2];
+        let url_escaped = url.replace(')', "%29");
+        
+        // Extract any text after the link (e.g., " (3x)")
+        let after_link = html[caps.get(0).unwrap().end()..].trim();
+        
+        let clean_suffix = normalize_whitespace(&strip_tags(after_link));
+        if clean_suffix.is_empty() {
+            return Ok(format!("[{}]({}{})", text, WIKI_BASE_URL, url_escaped));
+        } else {
+            return Ok(format!("[{}]({}{}) {}", text, WIKI_BASE_URL, url_escaped, clean_suffix));
+        }
+    }
+
+    Ok(strip_tags(html))
+}
+
+/// Remove the HTML tags from a cell fragment, keeping annotations that only exist as markup:
+/// icons (e.g. the Hard Mode symbol) become their alt text and line breaks become spaces.
+fn strip_tags(html: &str) -> String {
+    static IMG_RE: OnceLock<Regex> = OnceLock::new();
+    static BR_RE: OnceLock<Regex> = OnceLock::new();
+    static HTML_TAG_RE: OnceLock<Regex> = OnceLock::new();
+    let img_re = cached_regex(&IMG_RE, r#"<img\s[^>]*\balt="([^"]+)"[^>]*>"#);
+    let br_re = cached_regex(&BR_RE, r"<br\s*/?>");
+    let html_tag_re = cached_regex(&HTML_TAG_RE, r"<[^>]+>");
+
+    let text = img_re.replace_all(html, " $1 ");
+    let text = br_re.replace_all(&text, " ");
+    html_tag_re.replace_all(&text, "").to_string()
+}
+
+/// Convert a cell naming an area, splitting off its region if the cell contains one.
+///
+/// The region is taken from a second link (`<a>Area</a> (<a>Region</a>)`) or a parenthesized
+/// text suffix naming one of the REGIONS (`<a>Area</a> (Region)`); other suffixes like "(3x)" or
+/// "(2 copies)" are kept as part of the value.
+/// The returned value renders as "<area> (<region>)" when a region was found.
+pub fn parse_area_cell(html: &str) -> ParseResult<(String, Option<String>)> {
+    static LINK_RE: OnceLock<Regex> = OnceLock::new();
+    let link_re = cached_regex(&LINK_RE, r#"<a\s+[^>]*>(.+?)</a>"#);
+    let links: Vec<_> = link_re.find_iter(html).collect();
+    let Some(first) = links.first() else {
+        return Ok((convert_link(html)?, None));
+    };
+
+    let region = if let Some(second) = links.get(1) {
+        Some(strip_link(second.as_str())?)
+    } else {
+        let suffix = strip_markdown_links(html[first.end()..].trim());
+        suffix
+            .trim()
+            .strip_prefix('(')
+            .and_then(|rest| rest.strip_suffix(')'))
+            .map(str::trim)
+            .filter(|name| REGIONS.contains(name))
+            .map(str::to_string)
+    };
+
+    match region {
+        Some(region) if !region.is_empty() => {
+            let area = convert_link(first.as_str())?;
+            Ok((format!("{} ({})", area, region), Some(region)))
+        }
+        _ => Ok((convert_link(html)?, None)),
+    }
+}
+
+/// Extract the text of the first link in a wiki cell, dropping the URL.
+///
+/// Text after the link (e.g. " (3x)") is kept, cells without a link are returned with all tags removed.
+pub fn strip_link(html: &str) -> ParseResult<String> {
+    // Extract text from <a> tag without creating a link
+    static LINK_RE: OnceLock<Regex> = OnceLock::new();
+    let link_re = cached_regex(&LINK_RE, r#"<a\s+[^>]*>(.+?)</a>"#);
+    if let Some(caps) = link_re.captures(html) {
+        let text = caps[1].to_string();
+        
+        // Extract any text after the link (e.g., " (3x)")
+        let after_link = html[caps.get(0).unwrap().end()..].trim();
+        
+        let clean_suffix = normalize_whitespace(&strip_tags(after_link));
+        if clean_suffix.is_empty() {
+            return Ok(text);
+        } else {
+            return Ok(format!("{} {}", text, clean_suffix));
+        }
+    }
+
+    // Fallback: strip all HTML tags
+    Ok(strip_tags(html))
+}
+
+fn get_html(element: &scraper::ElementRef) -> String {
+    element.inner_html().trim().to_string()
+}
+
+pub fn strip_markdown_links(text: &str) -> String {
+    let re = Regex::new(r"\[(.+?)\]\((.+?)\)").unwrap();
+    let stripped = re.replace_all(text, "$1").to_string();
+    let html_re = Regex::new(r"<[^>]+>").unwrap();
+    html_re.replace_all(&stripped, "").to_string()
+}
+
+pub fn markdown_to_html_links(text: &str) -> String {
+    let re = Regex::new(r"\[(.+?)\]\((.+?)\)").unwrap();
+    re.replace_all(text, r#"<a href="$2">$1</a>"#).to_string()
+}
+
+/// The festival running at `now` according to the events page, e.g. "Wintersday 2025".
+///
+/// An entry counts if its date range ("December 18, 2025 – January 2, 2026") includes `now`,
+/// or, without a date range, if it's listed under the current events heading; festivals that
+/// are upcoming or over are skipped.
+pub fn get_current_event(body: &str, now: &DateTime<Utc>) -> Option<String> {
+    static CONTENT_SELECTOR: OnceLock<Selector> = OnceLock::new();
+    let document = Html::parse_document(body);
+    let today = now.date_naive();
+
+    let mut current_section = false;
+    for element in document.select(cached_selector(&CONTENT_SELECTOR, "div.mw-parser-output :is(h2, h3, li, p)")) {
+        if matches!(element.value().name(), "h2" | "h3") {
+            let heading = element.text().collect::<String>().to_lowercase();
+            current_section = heading.contains("current") || heading.contains("ongoing");
+            continue;
+        }
+
+        let Some(name) = element
+            .select(a_selector())
+            .map(|link| normalize_whitespace(&link.text().collect::<String>()))
+            .find(|text| {
+                let lower = text.to_lowercase();
+                FESTIVALS.iter().any(|festival| lower.contains(&festival.to_lowercase()))
+            })
+        else {
+            continue;
+        };
+
+        let active = match event_date_range(&element.text().collect::<String>()) {
+            Some((start, end)) => start <= today && today <= end,
+            None => current_section,
+        };
+        if active {
+            return Some(name);
+        }
+    }
+    None
+}
+
+/// The first and last day of an event entry, from the first two dates in its text
+fn event_date_range(text: &str) -> Option<(NaiveDate, NaiveDate)> {
+    static DATE_RE: OnceLock<Regex> = OnceLock::new();
+    let date_re = cached_regex(&DATE_RE, r"[A-Z][a-z]+ \d{1,2}, \d{4}|\d{1,2} [A-Z][a-z]+ \d{4}");
+    let mut dates = date_re.find_iter(text).filter_map(|date| {
+        NaiveDate::parse_from_str(date.as_str(), "%B %d, %Y")
+            .or_else(|_| NaiveDate::parse_from_str(date.as_str(), "%d %B %Y"))
+            .ok()
+    });
+    Some((dates.next()?, dates.next()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_german_daily_page() {
+        let html = include_str!("../tests/fixtures/daily_activities_de.html");
+        let page = ActivityPage::parse(html).unwrap();
+        let tbody = page.table().unwrap();
+        assert!(DailyColumns::from_header(&tbody, Language::De).extras.is_empty());
+        // The English names don't match the German headers, which would all end up as extra columns
+        assert_eq!(DailyColumns::from_header(&tbody, Language::En).extras.len(), 6);
+
+        let date = Utc.with_ymd_and_hms(2025, 11, 22, 16, 0, 0).unwrap();
+        let daily = get_daily_data(html, &date, &date, true, Language::De).unwrap();
+        assert_eq!(daily.row_date, "22. November 2025");
+        assert_eq!(strip_markdown_links(&daily.zm), "Jennurs Horde");
+        assert_eq!(strip_markdown_links(&daily.zv), "Nahpui-Viertel");
+        assert_eq!(strip_markdown_links(&daily.sb), "Justiziar Marron");
+        assert_eq!(strip_markdown_links(&daily.vq), "Bauer Hamnet");
+        assert_eq!(strip_markdown_links(&daily.ns), "Grawl-Halsketten");
+        assert!(daily.extras.is_empty());
+    }
+}
@@ -0,0 +1,101 @@
+//! End-to-end test of the fetch, parse and format pipeline against a mock wiki
+
+use tokio::process::Command;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const DAILY_FIXTURE: &str = include_str!("fixtures/daily_activities.html");
+const WEEKLY_FIXTURE: &str = include_str!("fixtures/weekly_activities.html");
+
+const EXPECTED_TXT: &str = "\
+Dailies for 22 November 2025
+
+Nicholas Sandford.....: Grawl Necklaces
+Vanguard Quest........: Farmer Hamnet
+Wanted................: Justiciar Marron
+
+Zaishen Mission.......: Jennur's Horde
+Zaishen Bounty........: Eldritch Ettin
+Zaishen Combat........: Guild Versus Guild
+Zaishen Vanquish......: Nahpui Quarter
+
+Weekly bonuses:
+Nicholas the Traveller: Frigid Hearts (3x)
+PvE Bonus.............: Zaishen Mission
+PvP Bonus.............: Alliance Battle
+";
+
+fn html(body: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_raw(body, "text/html; charset=UTF-8")
+}
+
+#[tokio::test]
+async fn fetch_and_format_from_mock_wiki() {
+    let server = MockServer::start().await;
+
+    // The first daily request fails to exercise the retry path
+    Mock::given(method("GET"))
+        .and(path("/wiki/Daily_activities"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/Daily_activities"))
+        .respond_with(html(DAILY_FIXTURE))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/Weekly_activities"))
+        .respond_with(html(WEEKLY_FIXTURE))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_sheepnet"))
+        .args(["--output-format", "txt", "--at-time", "2025-11-22T16:00:00Z", "--quiet"])
+        .args(["--wiki-url", &server.uri()])
+        .output()
+        .await
+        .expect("failed to run sheepnet");
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), EXPECTED_TXT);
+}
+
+#[tokio::test]
+async fn exit_code_on_parse_failure() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/Daily_activities"))
+        .respond_with(html(DAILY_FIXTURE))
+        .mount(&server)
+        .await;
+
+    // The fixture has no row for this date
+    let output = Command::new(env!("CARGO_BIN_EXE_sheepnet"))
+        .args(["--output-format", "txt", "--at-time", "2000-01-01T16:00:00Z", "--quiet"])
+        .args(["--wiki-url", &server.uri()])
+        .output()
+        .await
+        .expect("failed to run sheepnet");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No daily data found for 1 January 2000"));
+
+    // The same failure as a JSON object on stdout
+    let output = Command::new(env!("CARGO_BIN_EXE_sheepnet"))
+        .args(["--output-format", "txt", "--at-time", "2000-01-01T16:00:00Z", "--json-errors"])
+        .args(["--wiki-url", &server.uri()])
+        .output()
+        .await
+        .expect("failed to run sheepnet");
+
+    assert_eq!(output.status.code(), Some(1));
+    let error: serde_json::Value = serde_json::from_slice(&output.stdout).expect("stdout is a JSON object");
+    assert_eq!(error["kind"], "daily_row_not_found");
+    assert_eq!(error["error"], "No daily data found for 1 January 2000");
+}